@@ -1,5 +1,11 @@
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+/// A byte offset range `(start, end)` into the `Lexer`'s input.
+pub type Span = (usize, usize);
+
 #[derive(PartialEq, Debug)]
-enum Token<'a> {
+pub enum Token<'a> {
     // Basic Syntax Blocks
     Assign,    // Assignment "="
     Comma,     // Separator ","
@@ -29,6 +35,8 @@ enum Token<'a> {
     // Multi character types
     Ident(&'a str), // An identity
     Int(i64),       // An integer
+    Float(f64),     // A floating-point number
+    Str(String),    // A string literal, with escapes already resolved
 
     // Keywords - Declation
     Function, // Function statement "fn"
@@ -44,36 +52,84 @@ enum Token<'a> {
     Return,
 
     // Miscellaneous
-    Illegal, // Illegal token
-    EOF,     // End of File
+    EOF, // End of File
+}
+
+/// Errors that can occur while producing the next token, each carrying
+/// the span (and the offending character where relevant) so a caller
+/// such as the `repl` can point at the problem.
+#[derive(PartialEq, Debug)]
+pub enum LexError {
+    IllegalChar { ch: char, span: Span },
+    IntegerOverflow { span: Span },
+    InvalidNumberLiteral { span: Span },
+    UnterminatedString { span: Span },
+}
+
+/// The base a numeric literal is written in, selected by a `0x`/`0b`/`0o` prefix.
+#[derive(Clone, Copy)]
+enum Radix {
+    Binary,
+    Octal,
+    Hex,
+}
+
+impl Radix {
+    fn from_prefix(ch: char) -> Option<Radix> {
+        match ch {
+            'x' => Some(Radix::Hex),
+            'b' => Some(Radix::Binary),
+            'o' => Some(Radix::Octal),
+            _ => None,
+        }
+    }
+
+    fn base(self) -> u32 {
+        match self {
+            Radix::Binary => 2,
+            Radix::Octal => 8,
+            Radix::Hex => 16,
+        }
+    }
+
+    fn contains(self, ch: char) -> bool {
+        ch.is_digit(self.base())
+    }
 }
 
 #[derive(Debug)]
-struct Lexer<'a> {
-    input: &'a str,       // Data to be lexed
-    position: usize,      // Current position being lexed
-    read_position: usize, // next position to be lexed
-    ch: char,             // current char being lexed
+pub struct Lexer<'a> {
+    input: &'a str,                   // Data to be lexed
+    chars: Peekable<CharIndices<'a>>, // O(1) cursor over input, byte-indexed
+    position: usize,                  // Byte offset of the current char being lexed
+    read_position: usize,             // Byte offset of the next char to be lexed
+    ch: char,                         // current char being lexed
+    eof_sent: bool,                   // whether the iterator has already yielded EOF
 }
 
 impl<'a> Lexer<'a> {
     pub fn new(input: &'a str) -> Lexer {
         let mut lexer = Lexer {
             input: input,
+            chars: input.char_indices().peekable(),
             position: 0,
             read_position: 0,
             ch: '\0',
+            eof_sent: false,
         };
         lexer.read_char();
         return lexer;
     }
 
-    pub fn next_token(&mut self) -> Token {
+    pub fn next_token(&mut self) -> Result<(Token<'a>, Span), LexError> {
         // Skipping any whitespace
         while self.ch.is_whitespace() {
             self.read_char();
         }
 
+        // Every token starts here, whether it's a single char or a whole word/int.
+        let start = self.position;
+
         let token = match self.ch {
             // Basic Syntax
             ',' => Token::Comma,
@@ -120,36 +176,53 @@ impl<'a> Lexer<'a> {
             },
 
             // Keywords
-            ch if ch.is_alphabetic() => return self.read_word(),
+            ch if ch.is_alphabetic() => return Ok((self.read_word(), (start, self.position))),
 
             // Numbers
-            ch if ch.is_digit(10) => return self.read_int(),
+            ch if ch.is_digit(10) => {
+                let token = self.read_number(start)?;
+                return Ok((token, (start, self.position)));
+            }
+
+            // Strings
+            '"' => {
+                let token = self.read_string(start)?;
+                return Ok((token, (start, self.position)));
+            }
 
             // Miscelaneous
             '\0' => Token::EOF,
-            _ => Token::Illegal,
+            illegal => {
+                self.read_char();
+                return Err(LexError::IllegalChar {
+                    ch: illegal,
+                    span: (start, self.position),
+                });
+            }
         };
 
         self.read_char();
-        return token;
+        return Ok((token, (start, self.position)));
     }
 
     fn read_char(&mut self) {
-        match self.read_position >= self.input.len() {
-            true => self.ch = '\0',
-            false => self.ch = self.input.chars().nth(self.read_position).unwrap_or('\0'),
+        match self.chars.next() {
+            Some((idx, ch)) => {
+                self.position = idx;
+                self.read_position = idx + ch.len_utf8();
+                self.ch = ch;
+            }
+            None => {
+                self.position = self.read_position;
+                self.ch = '\0';
+            }
         }
-        self.position = self.read_position;
-        self.read_position += 1;
     }
 
-    fn peek_next(&self) -> char {
-        if self.read_position >= self.input.len() {
-            return '\0';
-        }
-        self.input.chars().nth(self.read_position).unwrap()
+    fn peek_next(&mut self) -> char {
+        self.chars.peek().map_or('\0', |&(_, ch)| ch)
     }
-    fn read_word(&mut self) -> Token {
+    fn read_word(&mut self) -> Token<'a> {
         // Start of char sequence
         let idx = self.position;
 
@@ -171,19 +244,129 @@ impl<'a> Lexer<'a> {
         };
     }
 
-    fn read_int(&mut self) -> Token {
-        // Start of int sequence
-        let idx = self.position;
+    fn read_string(&mut self, idx: usize) -> Result<Token<'a>, LexError> {
+        self.read_char(); // consume the opening '"'
+
+        let mut value = String::new();
+        loop {
+            match self.ch {
+                '"' => {
+                    self.read_char(); // consume the closing '"'
+                    return Ok(Token::Str(value));
+                }
+                '\0' => {
+                    return Err(LexError::UnterminatedString {
+                        span: (idx, self.position),
+                    });
+                }
+                '\\' => {
+                    self.read_char();
+                    match self.ch {
+                        'n' => value.push('\n'),
+                        't' => value.push('\t'),
+                        '"' => value.push('"'),
+                        '\\' => value.push('\\'),
+                        other => value.push(other),
+                    }
+                    self.read_char();
+                }
+                ch => {
+                    value.push(ch);
+                    self.read_char();
+                }
+            }
+        }
+    }
+
+    fn read_number(&mut self, idx: usize) -> Result<Token<'a>, LexError> {
+        // A `0x`/`0b`/`0o` prefix switches the rest of the digits to that radix.
+        if self.ch == '0' {
+            if let Some(radix) = Radix::from_prefix(self.peek_next()) {
+                self.read_char(); // consume '0'
+                self.read_char(); // consume the radix letter
+                let digits_start = self.position;
+                while radix.contains(self.ch) {
+                    self.read_char();
+                }
+                if self.position == digits_start {
+                    return Err(LexError::InvalidNumberLiteral {
+                        span: (idx, self.position),
+                    });
+                }
+                return i64::from_str_radix(&self.input[digits_start..self.position], radix.base())
+                    .map(Token::Int)
+                    .map_err(|_| LexError::IntegerOverflow {
+                        span: (idx, self.position),
+                    });
+            }
+        }
 
         // Identifying size of int sequence
         while self.ch.is_digit(10) {
             self.read_char();
         }
 
-        // Parsing sequence as an i64 and returning
-        return Token::Int(self.input[idx..self.position].parse::<i64>().unwrap());
+        // A `.` followed by a digit turns this into a float; anything else
+        // (a lone `.`, or a second one) is a malformed literal.
+        if self.ch == '.' {
+            if !self.peek_next().is_digit(10) {
+                return Err(LexError::InvalidNumberLiteral {
+                    span: (idx, self.position + 1),
+                });
+            }
+            self.read_char();
+            while self.ch.is_digit(10) {
+                self.read_char();
+            }
+            if self.ch == '.' {
+                return Err(LexError::InvalidNumberLiteral {
+                    span: (idx, self.position + 1),
+                });
+            }
+            return Ok(Token::Float(
+                self.input[idx..self.position].parse().unwrap(),
+            ));
+        }
+
+        // Parsing sequence as an i64, mapping overflow to a LexError
+        // instead of panicking on the `.unwrap()` this used to do.
+        match self.input[idx..self.position].parse::<i64>() {
+            Ok(n) => Ok(Token::Int(n)),
+            Err(_) => Err(LexError::IntegerOverflow {
+                span: (idx, self.position),
+            }),
+        }
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<Token<'a>, LexError>;
+
+    // Stops after EOF is yielded once, so a `for` loop or `.collect()`
+    // doesn't have to compare against `Token::EOF` itself. A lex error
+    // also ends iteration, same as EOF would, but is yielded (not swallowed)
+    // so a caller collecting into `Result<Vec<Token>, LexError>` can tell a
+    // truncated result from a clean one.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.eof_sent {
+            return None;
+        }
+
+        match self.next_token() {
+            Ok((token, _span)) => {
+                if token == Token::EOF {
+                    self.eof_sent = true;
+                }
+                Some(Ok(token))
+            }
+            Err(err) => {
+                self.eof_sent = true;
+                Some(Err(err))
+            }
+        }
     }
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -288,12 +471,103 @@ mod tests {
             Token::EOF,
         ];
 
-        let mut lexer = Lexer::new(input);
+        let tokens: Result<Vec<Token>, LexError> = Lexer::new(input).collect();
+        assert_eq!(tokens, Ok(parsed_correctly));
+    }
 
-        for expected_token in parsed_correctly.into_iter() {
-            let token = lexer.next_token();
-            println!("{:?} Ã© igual a {:?}", token, expected_token);
-            assert_eq!(token, expected_token)
-        }
+    #[test]
+    fn tokens_carry_accurate_byte_spans() {
+        let mut lexer = Lexer::new("foo == bar");
+        assert_eq!(lexer.next_token(), Ok((Token::Ident("foo"), (0, 3))));
+        assert_eq!(lexer.next_token(), Ok((Token::Eq, (4, 6))));
+        assert_eq!(lexer.next_token(), Ok((Token::Ident("bar"), (7, 10))));
+    }
+
+    #[test]
+    fn iterator_surfaces_lex_errors_instead_of_truncating_silently() {
+        let tokens: Result<Vec<Token>, LexError> = Lexer::new("let x = 5; @").collect();
+        assert_eq!(
+            tokens,
+            Err(LexError::IllegalChar {
+                ch: '@',
+                span: (11, 12)
+            })
+        );
+    }
+
+    #[test]
+    fn integer_overflow_is_a_lex_error() {
+        let mut lexer = Lexer::new("99999999999999999999");
+        assert_eq!(
+            lexer.next_token(),
+            Err(LexError::IntegerOverflow { span: (0, 20) })
+        );
+    }
+
+    #[test]
+    fn numeric_literals_support_radix_prefixes_and_floats() {
+        let tokens: Result<Vec<Token>, LexError> = Lexer::new("0x1F 0b1010 0o17 3.14").collect();
+        assert_eq!(
+            tokens,
+            Ok(vec![
+                Token::Int(31),
+                Token::Int(10),
+                Token::Int(15),
+                Token::Float(3.14),
+                Token::EOF,
+            ])
+        );
+    }
+
+    #[test]
+    fn radix_prefix_with_no_digits_is_a_lex_error() {
+        let mut lexer = Lexer::new("0x ");
+        assert_eq!(
+            lexer.next_token(),
+            Err(LexError::InvalidNumberLiteral { span: (0, 2) })
+        );
+    }
+
+    #[test]
+    fn a_second_decimal_point_is_a_lex_error() {
+        let mut lexer = Lexer::new("1.2.3");
+        assert_eq!(
+            lexer.next_token(),
+            Err(LexError::InvalidNumberLiteral { span: (0, 4) })
+        );
+    }
+
+    #[test]
+    fn string_literals_resolve_escapes() {
+        let mut lexer = Lexer::new(r#""a\nb\tc\"d\\e""#);
+        assert_eq!(
+            lexer.next_token(),
+            Ok((Token::Str("a\nb\tc\"d\\e".to_string()), (0, 15)))
+        );
+    }
+
+    #[test]
+    fn unterminated_string_is_a_lex_error() {
+        let mut lexer = Lexer::new("\"abc");
+        assert_eq!(
+            lexer.next_token(),
+            Err(LexError::UnterminatedString { span: (0, 4) })
+        );
+    }
+
+    #[test]
+    fn unicode_identifiers_and_strings_lex_without_panicking() {
+        let tokens: Result<Vec<Token>, LexError> = Lexer::new(r#"let café = "caffè";"#).collect();
+        assert_eq!(
+            tokens,
+            Ok(vec![
+                Token::Let,
+                Token::Ident("café"),
+                Token::Assign,
+                Token::Str("caffè".to_string()),
+                Token::Semicolon,
+                Token::EOF,
+            ])
+        );
     }
 }
@@ -0,0 +1,41 @@
+/// The root node every parse produces: a sequence of top level statements.
+#[derive(Debug, PartialEq)]
+pub struct Program {
+    pub statements: Vec<Statement>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Statement {
+    Let { name: String, value: Expression },
+    Return { value: Expression },
+    ExpressionStatement(Expression),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Expression {
+    Identifier(String),
+    IntegerLiteral(i64),
+    Boolean(bool),
+    Prefix {
+        operator: String,
+        right: Box<Expression>,
+    },
+    Infix {
+        left: Box<Expression>,
+        operator: String,
+        right: Box<Expression>,
+    },
+    If {
+        condition: Box<Expression>,
+        consequence: Vec<Statement>,
+        alternative: Option<Vec<Statement>>,
+    },
+    FunctionLiteral {
+        parameters: Vec<String>,
+        body: Vec<Statement>,
+    },
+    Call {
+        function: Box<Expression>,
+        arguments: Vec<Expression>,
+    },
+}
@@ -0,0 +1,561 @@
+pub mod ast;
+
+use crate::lexer::{LexError, Lexer, Span, Token};
+use ast::{Expression, Program, Statement};
+
+/// An error produced while parsing, carrying the span of the token that
+/// triggered it so a caller can point back at the source.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl ParseError {
+    fn from_lex_error(err: LexError) -> ParseError {
+        let (message, span) = match err {
+            LexError::IllegalChar { ch, span } => (format!("illegal character {:?}", ch), span),
+            LexError::IntegerOverflow { span } => (
+                "integer literal too large to fit in an i64".to_string(),
+                span,
+            ),
+            LexError::InvalidNumberLiteral { span } => {
+                ("invalid numeric literal".to_string(), span)
+            }
+            LexError::UnterminatedString { span } => {
+                ("unterminated string literal".to_string(), span)
+            }
+        };
+        ParseError { message, span }
+    }
+}
+
+// LOWEST < EQUALS < LESSGREATER < SUM < PRODUCT < PREFIX < CALL
+#[derive(PartialEq, PartialOrd, Clone, Copy)]
+enum Precedence {
+    Lowest,
+    Equals,
+    LessGreater,
+    Sum,
+    Product,
+    Prefix,
+    Call,
+}
+
+fn token_precedence(token: &Token) -> Precedence {
+    match token {
+        Token::Eq | Token::NotEq => Precedence::Equals,
+        Token::LT | Token::GT | Token::LTE | Token::GTE => Precedence::LessGreater,
+        Token::Plus | Token::Minus => Precedence::Sum,
+        Token::Asterisk | Token::Slash => Precedence::Product,
+        Token::LParen => Precedence::Call,
+        _ => Precedence::Lowest,
+    }
+}
+
+fn operator_str(token: &Token) -> &'static str {
+    match token {
+        Token::Plus => "+",
+        Token::Minus => "-",
+        Token::Asterisk => "*",
+        Token::Slash => "/",
+        Token::LT => "<",
+        Token::GT => ">",
+        Token::LTE => "<=",
+        Token::GTE => ">=",
+        Token::Eq => "==",
+        Token::NotEq => "!=",
+        Token::Bang => "!",
+        _ => "?",
+    }
+}
+
+pub struct Parser<'a> {
+    lexer: Lexer<'a>,
+    cur_token: Token<'a>,
+    cur_span: Span,
+    peek_token: Token<'a>,
+    peek_span: Span,
+    errors: Vec<ParseError>,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(lexer: Lexer<'a>) -> Parser<'a> {
+        let mut parser = Parser {
+            lexer,
+            cur_token: Token::EOF,
+            cur_span: (0, 0),
+            peek_token: Token::EOF,
+            peek_span: (0, 0),
+            errors: Vec::new(),
+        };
+        // Prime cur_token/peek_token, same as calling next_token twice.
+        parser.advance();
+        parser.advance();
+        parser
+    }
+
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    pub fn parse_program(&mut self) -> Program {
+        let mut statements = Vec::new();
+
+        while !self.cur_token_is(&Token::EOF) {
+            if let Some(statement) = self.parse_statement() {
+                statements.push(statement);
+            }
+            self.advance();
+        }
+
+        Program { statements }
+    }
+
+    fn advance(&mut self) {
+        self.cur_token = std::mem::replace(&mut self.peek_token, Token::EOF);
+        self.cur_span = self.peek_span;
+
+        // Lex errors are recorded and skipped over rather than aborting the parse.
+        loop {
+            match self.lexer.next_token() {
+                Ok((token, span)) => {
+                    self.peek_token = token;
+                    self.peek_span = span;
+                    break;
+                }
+                Err(err) => self.errors.push(ParseError::from_lex_error(err)),
+            }
+        }
+    }
+
+    fn cur_token_is(&self, token: &Token) -> bool {
+        &self.cur_token == token
+    }
+
+    fn peek_token_is(&self, token: &Token) -> bool {
+        &self.peek_token == token
+    }
+
+    fn cur_precedence(&self) -> Precedence {
+        token_precedence(&self.cur_token)
+    }
+
+    fn peek_precedence(&self) -> Precedence {
+        token_precedence(&self.peek_token)
+    }
+
+    fn expect_peek(&mut self, expected: &Token) -> bool {
+        if self.peek_token_is(expected) {
+            self.advance();
+            true
+        } else {
+            self.errors.push(ParseError {
+                message: format!(
+                    "expected next token to be {:?}, got {:?} instead",
+                    expected, self.peek_token
+                ),
+                span: self.peek_span,
+            });
+            false
+        }
+    }
+
+    fn parse_statement(&mut self) -> Option<Statement> {
+        match self.cur_token {
+            Token::Let => self.parse_let_statement(),
+            Token::Return => self.parse_return_statement(),
+            _ => self.parse_expression_statement(),
+        }
+    }
+
+    fn parse_let_statement(&mut self) -> Option<Statement> {
+        let name = match &self.peek_token {
+            Token::Ident(name) => name.to_string(),
+            _ => {
+                self.errors.push(ParseError {
+                    message: format!(
+                        "expected identifier after let, got {:?} instead",
+                        self.peek_token
+                    ),
+                    span: self.peek_span,
+                });
+                return None;
+            }
+        };
+        self.advance(); // cur_token is now the identifier
+
+        if !self.expect_peek(&Token::Assign) {
+            return None;
+        }
+        self.advance(); // cur_token is now the first token of the value
+
+        let value = self.parse_expression(Precedence::Lowest)?;
+
+        if self.peek_token_is(&Token::Semicolon) {
+            self.advance();
+        }
+
+        Some(Statement::Let { name, value })
+    }
+
+    fn parse_return_statement(&mut self) -> Option<Statement> {
+        self.advance(); // consume 'return'
+
+        let value = self.parse_expression(Precedence::Lowest)?;
+
+        if self.peek_token_is(&Token::Semicolon) {
+            self.advance();
+        }
+
+        Some(Statement::Return { value })
+    }
+
+    fn parse_expression_statement(&mut self) -> Option<Statement> {
+        let expression = self.parse_expression(Precedence::Lowest)?;
+
+        if self.peek_token_is(&Token::Semicolon) {
+            self.advance();
+        }
+
+        Some(Statement::ExpressionStatement(expression))
+    }
+
+    fn parse_block_statement(&mut self) -> Vec<Statement> {
+        self.advance(); // consume '{'
+
+        let mut statements = Vec::new();
+        while !self.cur_token_is(&Token::RBrace) && !self.cur_token_is(&Token::EOF) {
+            if let Some(statement) = self.parse_statement() {
+                statements.push(statement);
+            }
+            self.advance();
+        }
+
+        statements
+    }
+
+    fn parse_expression(&mut self, precedence: Precedence) -> Option<Expression> {
+        let mut left = self.parse_prefix()?;
+
+        while !self.peek_token_is(&Token::Semicolon) && precedence < self.peek_precedence() {
+            self.advance();
+            left = self.parse_infix(left)?;
+        }
+
+        Some(left)
+    }
+
+    fn parse_prefix(&mut self) -> Option<Expression> {
+        match &self.cur_token {
+            Token::Ident(name) => Some(Expression::Identifier(name.to_string())),
+            Token::Int(value) => Some(Expression::IntegerLiteral(*value)),
+            Token::True => Some(Expression::Boolean(true)),
+            Token::False => Some(Expression::Boolean(false)),
+            Token::Bang | Token::Minus => self.parse_prefix_expression(),
+            Token::LParen => self.parse_grouped_expression(),
+            Token::If => self.parse_if_expression(),
+            Token::Function => self.parse_function_literal(),
+            other => {
+                self.errors.push(ParseError {
+                    message: format!("no prefix parse function for {:?}", other),
+                    span: self.cur_span,
+                });
+                None
+            }
+        }
+    }
+
+    fn parse_infix(&mut self, left: Expression) -> Option<Expression> {
+        match self.cur_token {
+            Token::LParen => self.parse_call_expression(left),
+            _ => self.parse_infix_expression(left),
+        }
+    }
+
+    fn parse_prefix_expression(&mut self) -> Option<Expression> {
+        let operator = operator_str(&self.cur_token).to_string();
+        self.advance();
+        let right = self.parse_expression(Precedence::Prefix)?;
+        Some(Expression::Prefix {
+            operator,
+            right: Box::new(right),
+        })
+    }
+
+    fn parse_infix_expression(&mut self, left: Expression) -> Option<Expression> {
+        let operator = operator_str(&self.cur_token).to_string();
+        let precedence = self.cur_precedence();
+        self.advance();
+        let right = self.parse_expression(precedence)?;
+        Some(Expression::Infix {
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+        })
+    }
+
+    fn parse_grouped_expression(&mut self) -> Option<Expression> {
+        self.advance(); // consume '('
+        let expression = self.parse_expression(Precedence::Lowest)?;
+        if !self.expect_peek(&Token::RParen) {
+            return None;
+        }
+        Some(expression)
+    }
+
+    fn parse_if_expression(&mut self) -> Option<Expression> {
+        if !self.expect_peek(&Token::LParen) {
+            return None;
+        }
+        self.advance(); // cur_token is now the first token of the condition
+
+        let condition = self.parse_expression(Precedence::Lowest)?;
+
+        if !self.expect_peek(&Token::RParen) {
+            return None;
+        }
+        if !self.expect_peek(&Token::LBrace) {
+            return None;
+        }
+        let consequence = self.parse_block_statement();
+
+        let alternative = if self.peek_token_is(&Token::Else) {
+            self.advance();
+            if !self.expect_peek(&Token::LBrace) {
+                return None;
+            }
+            Some(self.parse_block_statement())
+        } else {
+            None
+        };
+
+        Some(Expression::If {
+            condition: Box::new(condition),
+            consequence,
+            alternative,
+        })
+    }
+
+    fn parse_function_literal(&mut self) -> Option<Expression> {
+        if !self.expect_peek(&Token::LParen) {
+            return None;
+        }
+        let parameters = self.parse_function_parameters()?;
+
+        if !self.expect_peek(&Token::LBrace) {
+            return None;
+        }
+        let body = self.parse_block_statement();
+
+        Some(Expression::FunctionLiteral { parameters, body })
+    }
+
+    fn parse_function_parameters(&mut self) -> Option<Vec<String>> {
+        let mut parameters = Vec::new();
+
+        if self.peek_token_is(&Token::RParen) {
+            self.advance();
+            return Some(parameters);
+        }
+
+        self.advance();
+        parameters.push(self.parse_parameter_name()?);
+
+        while self.peek_token_is(&Token::Comma) {
+            self.advance();
+            self.advance();
+            parameters.push(self.parse_parameter_name()?);
+        }
+
+        if !self.expect_peek(&Token::RParen) {
+            return None;
+        }
+
+        Some(parameters)
+    }
+
+    fn parse_parameter_name(&mut self) -> Option<String> {
+        match &self.cur_token {
+            Token::Ident(name) => Some(name.to_string()),
+            other => {
+                self.errors.push(ParseError {
+                    message: format!("expected a parameter name, got {:?} instead", other),
+                    span: self.cur_span,
+                });
+                None
+            }
+        }
+    }
+
+    fn parse_call_expression(&mut self, function: Expression) -> Option<Expression> {
+        let arguments = self.parse_call_arguments()?;
+        Some(Expression::Call {
+            function: Box::new(function),
+            arguments,
+        })
+    }
+
+    fn parse_call_arguments(&mut self) -> Option<Vec<Expression>> {
+        let mut arguments = Vec::new();
+
+        if self.peek_token_is(&Token::RParen) {
+            self.advance();
+            return Some(arguments);
+        }
+
+        self.advance();
+        arguments.push(self.parse_expression(Precedence::Lowest)?);
+
+        while self.peek_token_is(&Token::Comma) {
+            self.advance();
+            self.advance();
+            arguments.push(self.parse_expression(Precedence::Lowest)?);
+        }
+
+        if !self.expect_peek(&Token::RParen) {
+            return None;
+        }
+
+        Some(arguments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::ast::{Expression, Statement};
+
+    fn parse(input: &str) -> Program {
+        let mut parser = Parser::new(Lexer::new(input));
+        let program = parser.parse_program();
+        assert_eq!(parser.errors(), &[], "unexpected parse errors");
+        program
+    }
+
+    #[test]
+    fn let_and_if_statements() {
+        let program = parse("let x = 5; if (x < 10) { return x; } else { return 0; }");
+
+        assert_eq!(
+            program.statements[0],
+            Statement::Let {
+                name: "x".to_string(),
+                value: Expression::IntegerLiteral(5),
+            }
+        );
+
+        match &program.statements[1] {
+            Statement::ExpressionStatement(Expression::If {
+                condition,
+                consequence,
+                alternative,
+            }) => {
+                assert_eq!(
+                    **condition,
+                    Expression::Infix {
+                        left: Box::new(Expression::Identifier("x".to_string())),
+                        operator: "<".to_string(),
+                        right: Box::new(Expression::IntegerLiteral(10)),
+                    }
+                );
+                assert_eq!(
+                    *consequence,
+                    vec![Statement::Return {
+                        value: Expression::Identifier("x".to_string()),
+                    }]
+                );
+                assert_eq!(
+                    *alternative,
+                    Some(vec![Statement::Return {
+                        value: Expression::IntegerLiteral(0),
+                    }])
+                );
+            }
+            other => panic!("expected an if expression statement, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn operator_precedence() {
+        let program = parse("5 < 10 > 5;");
+
+        assert_eq!(
+            program.statements[0],
+            Statement::ExpressionStatement(Expression::Infix {
+                left: Box::new(Expression::Infix {
+                    left: Box::new(Expression::IntegerLiteral(5)),
+                    operator: "<".to_string(),
+                    right: Box::new(Expression::IntegerLiteral(10)),
+                }),
+                operator: ">".to_string(),
+                right: Box::new(Expression::IntegerLiteral(5)),
+            })
+        );
+    }
+
+    #[test]
+    fn call_expression_arguments() {
+        let program = parse("add(1, 2 * 3);");
+
+        assert_eq!(
+            program.statements[0],
+            Statement::ExpressionStatement(Expression::Call {
+                function: Box::new(Expression::Identifier("add".to_string())),
+                arguments: vec![
+                    Expression::IntegerLiteral(1),
+                    Expression::Infix {
+                        left: Box::new(Expression::IntegerLiteral(2)),
+                        operator: "*".to_string(),
+                        right: Box::new(Expression::IntegerLiteral(3)),
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn function_literal_parameters() {
+        let program = parse("fn(x, y) { x + y; }");
+
+        assert_eq!(
+            program.statements[0],
+            Statement::ExpressionStatement(Expression::FunctionLiteral {
+                parameters: vec!["x".to_string(), "y".to_string()],
+                body: vec![Statement::ExpressionStatement(Expression::Infix {
+                    left: Box::new(Expression::Identifier("x".to_string())),
+                    operator: "+".to_string(),
+                    right: Box::new(Expression::Identifier("y".to_string())),
+                })],
+            })
+        );
+    }
+
+    #[test]
+    fn prefix_expressions() {
+        let program = parse("!x; -5;");
+
+        assert_eq!(
+            program.statements[0],
+            Statement::ExpressionStatement(Expression::Prefix {
+                operator: "!".to_string(),
+                right: Box::new(Expression::Identifier("x".to_string())),
+            })
+        );
+        assert_eq!(
+            program.statements[1],
+            Statement::ExpressionStatement(Expression::Prefix {
+                operator: "-".to_string(),
+                right: Box::new(Expression::IntegerLiteral(5)),
+            })
+        );
+    }
+
+    #[test]
+    fn malformed_let_statement_is_recorded_as_a_parse_error() {
+        let mut parser = Parser::new(Lexer::new("let = 5;"));
+        parser.parse_program();
+
+        assert!(!parser.errors().is_empty());
+    }
+}
@@ -1,4 +1,5 @@
-use crate::lexer::{Lexer, Token};
+use crate::lexer::Lexer;
+use crate::parser::{ParseError, Parser};
 use std::io::{stdin, stdout, Write};
 
 pub fn repl() {
@@ -9,15 +10,28 @@ pub fn repl() {
         stdout().flush().unwrap();
         stdin().read_line(&mut s).unwrap();
 
-        let mut lexer = Lexer::new(&s);
-        loop {
-            let token = lexer.next_token();
-            println!("{:?}", token);
+        let mut parser = Parser::new(Lexer::new(&s));
+        let program = parser.parse_program();
 
-            if token == Token::EOF {
-                break;
+        if parser.errors().is_empty() {
+            println!("{:#?}", program);
+        } else {
+            for error in parser.errors() {
+                print_parse_error(&s, error);
             }
         }
+
         s.clear();
     }
 }
+
+fn print_parse_error(input: &str, error: &ParseError) {
+    let (start, end) = error.span;
+    println!("{}", input.trim_end_matches('\n'));
+    println!(
+        "{}{}",
+        " ".repeat(start),
+        "^".repeat(end.saturating_sub(start).max(1))
+    );
+    println!("{} at {}..{}", error.message, start, end);
+}